@@ -1,11 +1,15 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
 use std::process::Stdio;
 #[cfg(windows)]
 use tokio::signal;
 #[cfg(unix)]
 use tokio::signal::unix::{signal, SignalKind};
+use tokio::task::JoinHandle;
 
-use crate::strategies::base::{ExecutionContext, IsolationStrategy};
+use crate::events::{EventKind, EventSink};
+use crate::strategies::base::{ExecutionContext, IsolationStrategy, OutputMode, StdinSource};
+use crate::watch::{OnBusyPolicy, WatchOptions, RESTART_GRACE_PERIOD};
 
 #[cfg(not(any(target_os = "linux", target_os = "windows", target_os = "macos")))]
 use crate::strategies::host::HostStrategy;
@@ -13,16 +17,48 @@ use crate::strategies::host::HostStrategy;
 use crate::strategies::linux::LinuxBwrapStrategy;
 #[cfg(target_os = "macos")]
 use crate::strategies::macos::MacOsSandboxStrategy;
+#[cfg(windows)]
+use crate::strategies::windows::WindowsJob;
 #[cfg(target_os = "windows")]
 use crate::strategies::windows::WindowsJobStrategy;
 
+/// Result of a single `Engine::run` call.
+///
+/// `stdout`/`stderr` are only populated when `ExecutionContext::output_mode`
+/// is `Capture` or `Tee`; under the default `Inherit` mode they're empty.
+pub struct RunOutput {
+    pub code: i32,
+    pub stdout: Vec<u8>,
+    pub stderr: Vec<u8>,
+}
+
 /// The core engine that drives the process execution.
 /// It handles process spawning, IO pumping (piping stdout/stderr), and signal handling.
 pub struct Engine {
     strategy: Box<dyn IsolationStrategy>,
+    events: EventSink,
+    grace_period: std::time::Duration,
 }
 
 impl Engine {
+    /// Attaches an NDJSON event sink that receives lifecycle/IO events for every run.
+    #[must_use]
+    pub fn with_events(mut self, events: EventSink) -> Self {
+        self.events = events;
+        self
+    }
+
+    /// Overrides how long `graceful_terminate` waits after SIGTERM (Unix) or
+    /// closing the child (Windows) before escalating to a hard kill. Applies
+    /// to watch-mode restarts and cancellation, and to `run_managed`
+    /// shutdowns; `run` uses `ctx.shutdown_timeout` instead. Defaults to
+    /// `RESTART_GRACE_PERIOD`.
+    #[must_use]
+    pub fn with_grace_period(mut self, grace_period: std::time::Duration) -> Self {
+        self.grace_period = grace_period;
+        self
+    }
+
     /// Creates a new Engine instance with the appropriate isolation strategy for the current OS.
     pub fn new(sandbox: bool) -> Self {
         let strategy: Box<dyn IsolationStrategy> = if sandbox {
@@ -32,7 +68,7 @@ impl Engine {
             }
             #[cfg(target_os = "windows")]
             {
-                Box::new(WindowsJobStrategy)
+                Box::new(WindowsJobStrategy::default())
             }
             #[cfg(target_os = "macos")]
             {
@@ -48,73 +84,816 @@ impl Engine {
             // or a specific HostStrategy implementation.
             Box::new(crate::strategies::host::HostStrategy)
         };
-        Engine { strategy }
+        Engine {
+            strategy,
+            events: EventSink::disabled(),
+            grace_period: RESTART_GRACE_PERIOD,
+        }
     }
 
     /// Executes the command defined in `ctx` and manages its lifecycle.
     ///
     /// This method ensures that:
     /// 1. STDOUT and STDERR are fully read until EOF.
-    /// 2. The process is killed if a cancellation signal (Ctrl+C) is received.
+    /// 2. SIGTERM/SIGINT/SIGHUP/SIGQUIT/SIGUSR1/SIGUSR2 received by the
+    ///    launcher are forwarded to the child's whole process group (just the
+    ///    child itself when `ctx.kill_process_group` is false), with
+    ///    termination signals escalating to SIGKILL after `ctx.shutdown_timeout`.
     /// 3. No zombie processes remain (waiting for exit status).
-    pub async fn run(&self, ctx: ExecutionContext) -> Result<i32> {
-        let cmd_res = self.strategy.build_command(&ctx);
-        if let Err(e) = cmd_res {
-            return Err(e);
+    pub async fn run(&self, ctx: ExecutionContext) -> Result<RunOutput> {
+        let mut ctx = ctx;
+
+        #[cfg(unix)]
+        let (mut child, stdout_task, stderr_task, stdin_task, _jobserver) =
+            self.spawn_for_run(&mut ctx)?;
+        #[cfg(not(unix))]
+        let (mut child, stdout_task, stderr_task, stdin_task) = self.spawn_for_run(&mut ctx)?;
+        // Must run after spawn: the job can only be assigned to a live
+        // process (see `IsolationStrategy::assign_job`).
+        #[cfg(windows)]
+        let windows_job = self.strategy.assign_job(&child);
+
+        #[cfg(unix)]
+        let outcome = {
+            // When `kill_process_group` is set the child is its own process
+            // group leader (see `spawn_piped`), so its pgid equals its pid.
+            let pid = child
+                .id()
+                .map(|pid| pid as i32)
+                .context("child exited before its pid could be read")?;
+            wait_with_signal_forwarding(
+                &mut child,
+                pid,
+                ctx.kill_process_group,
+                ctx.shutdown_timeout,
+                &self.events,
+                &ctx.id,
+            )
+            .await
+        };
+        #[cfg(windows)]
+        let outcome = wait_with_signal_forwarding(
+            &mut child,
+            ctx.shutdown_timeout,
+            &self.events,
+            &ctx.id,
+            windows_job.as_ref(),
+        )
+        .await;
+
+        // CRITICAL: Wait for IO tasks to finish flushing buffers before exiting.
+        // This prevents race conditions where the process exits but data is still in the pipe.
+        let (stdout, stderr) = match tokio::join!(stdout_task, stderr_task) {
+            (Ok(stdout), Ok(stderr)) => (stdout, stderr),
+            _ => (Vec::new(), Vec::new()),
+        };
+        // A child that exits before reading all of stdin closes its end of
+        // the pipe, which surfaces here as a broken-pipe write error; that's
+        // an ordinary early-exit rather than a run failure, so it's ignored.
+        if let Some(stdin_task) = stdin_task {
+            let _ = stdin_task.await;
         }
-        let cmd = cmd_res.unwrap();
 
-        let mut child = tokio::process::Command::from(cmd)
+        match outcome {
+            Ok(TerminationOutcome::Graceful(s)) => {
+                let (code, signal) = exit_event_fields(&s);
+                self.events
+                    .emit(&ctx.id, EventKind::ProcessExited { code, signal });
+                Ok(RunOutput {
+                    code: code.unwrap_or(-1),
+                    stdout,
+                    stderr,
+                })
+            }
+            // Forwarded signal went unanswered past `shutdown_timeout` and
+            // the child had to be SIGKILLed — report the same sentinel
+            // `watch`/`run_managed` use so callers can tell this apart from
+            // an ordinary exit code.
+            Ok(TerminationOutcome::ForceKilled) => {
+                self.events.emit(
+                    &ctx.id,
+                    EventKind::ProcessExited {
+                        code: Some(FORCE_KILLED_EXIT_CODE),
+                        signal: None,
+                    },
+                );
+                Ok(RunOutput {
+                    code: FORCE_KILLED_EXIT_CODE,
+                    stdout,
+                    stderr,
+                })
+            }
+            Err(e) => Err(anyhow!("Wait failed: {}", e)),
+        }
+    }
+
+    /// Like `run`, but for children supervised by a [`crate::manager::Manager`]:
+    /// shutdown is driven by `shutdown` going `true` (the `Manager` broadcasts
+    /// this to every live child at once) instead of this method installing
+    /// its own OS signal handlers, since the `Manager` owns exactly one
+    /// signal listener for the whole fleet. Termination still runs the same
+    /// SIGTERM-then-SIGKILL escalation as `run`, via `graceful_terminate`.
+    pub(crate) async fn run_managed(
+        &self,
+        ctx: ExecutionContext,
+        mut shutdown: tokio::sync::watch::Receiver<bool>,
+    ) -> Result<RunOutput> {
+        let mut ctx = ctx;
+
+        #[cfg(unix)]
+        let (mut child, stdout_task, stderr_task, stdin_task, _jobserver) =
+            self.spawn_for_run(&mut ctx)?;
+        #[cfg(not(unix))]
+        let (mut child, stdout_task, stderr_task, stdin_task) = self.spawn_for_run(&mut ctx)?;
+        #[cfg(windows)]
+        let windows_job = self.strategy.assign_job(&child);
+
+        let code = tokio::select! {
+            status = wait_child(&mut child) => match status {
+                Ok(s) => s.code().unwrap_or(-1),
+                Err(e) => return Err(anyhow!("Wait failed: {}", e)),
+            },
+            _ = async { let _ = shutdown.wait_for(|shutting_down| *shutting_down).await; } => {
+                graceful_terminate(
+                    &mut child,
+                    self.grace_period,
+                    ctx.kill_process_group,
+                    #[cfg(windows)]
+                    windows_job.as_ref(),
+                )
+                .await
+                .exit_code()
+            }
+        };
+
+        let (stdout, stderr) = match tokio::join!(stdout_task, stderr_task) {
+            (Ok(stdout), Ok(stderr)) => (stdout, stderr),
+            _ => (Vec::new(), Vec::new()),
+        };
+        if let Some(stdin_task) = stdin_task {
+            let _ = stdin_task.await;
+        }
+
+        self.events.emit(
+            &ctx.id,
+            EventKind::ProcessExited {
+                code: Some(code),
+                signal: None,
+            },
+        );
+        Ok(RunOutput { code, stdout, stderr })
+    }
+
+    /// Shared setup for `run`/`run_managed`: stands up a jobserver if
+    /// `ctx.jobs` asks for one, emits `StrategySelected`, spawns `ctx`'s
+    /// command, and emits `ProcessStarted`. Factored out so the two methods
+    /// can't silently diverge on this setup the way `watch` once did.
+    ///
+    /// On Unix the returned `Jobserver` (if any) must be kept bound by the
+    /// caller until the child has been waited on — dropping it early closes
+    /// the pipe and invalidates any outstanding tokens.
+    #[cfg(unix)]
+    fn spawn_for_run(
+        &self,
+        ctx: &mut ExecutionContext,
+    ) -> Result<(
+        tokio::process::Child,
+        JoinHandle<Vec<u8>>,
+        JoinHandle<Vec<u8>>,
+        Option<JoinHandle<()>>,
+        Option<crate::jobserver::Jobserver>,
+    )> {
+        // If requested, stand up a jobserver and hand its auth string to the
+        // child through the environment so nested `cargo`/`make`/`ninja`
+        // invocations acquire a token before spawning their own workers
+        // instead of oversubscribing the host.
+        let jobserver = match ctx.jobs {
+            Some(n) if n >= 1 => {
+                let js = crate::jobserver::Jobserver::new(n)?;
+                let auth = js.makeflags_value();
+                ctx.env_vars.insert("MAKEFLAGS".to_string(), auth.clone());
+                ctx.env_vars.insert("CARGO_MAKEFLAGS".to_string(), auth);
+                Some(js)
+            }
+            _ => None,
+        };
+
+        self.events.emit(
+            &ctx.id,
+            EventKind::StrategySelected {
+                strategy: self.strategy.name().to_string(),
+            },
+        );
+
+        let stdin = std::mem::take(&mut ctx.stdin);
+        let (mut child, stdout_task, stderr_task, stdin_task) =
+            self.spawn_piped(ctx, stdin, jobserver.as_ref())?;
+        if let Some(pid) = child.id() {
+            self.events.emit(&ctx.id, EventKind::ProcessStarted { pid });
+        }
+
+        Ok((child, stdout_task, stderr_task, stdin_task, jobserver))
+    }
+
+    #[cfg(not(unix))]
+    fn spawn_for_run(
+        &self,
+        ctx: &mut ExecutionContext,
+    ) -> Result<(
+        tokio::process::Child,
+        JoinHandle<Vec<u8>>,
+        JoinHandle<Vec<u8>>,
+        Option<JoinHandle<()>>,
+    )> {
+        self.events.emit(
+            &ctx.id,
+            EventKind::StrategySelected {
+                strategy: self.strategy.name().to_string(),
+            },
+        );
+
+        let stdin = std::mem::take(&mut ctx.stdin);
+        let (mut child, stdout_task, stderr_task, stdin_task) = self.spawn_piped(ctx, stdin)?;
+        if let Some(pid) = child.id() {
+            self.events.emit(&ctx.id, EventKind::ProcessStarted { pid });
+        }
+
+        Ok((child, stdout_task, stderr_task, stdin_task))
+    }
+
+    /// Builds and spawns `ctx`'s command against the given `stdin` source,
+    /// piping stdout/stderr (and, if `stdin` needs it, stdin) through the
+    /// bridging tasks shared by `run` and `watch`. `stdin` is taken
+    /// separately from `ctx` since it's a one-shot resource — see
+    /// `StdinSource`'s `Clone` impl.
+    ///
+    /// On Unix, unless `ctx.kill_process_group` is `false`, the child is made
+    /// the leader of its own process group (`process_group(0)`) so signals
+    /// can be forwarded to the whole tree it spawns rather than just the
+    /// single process the strategy launched.
+    fn spawn_piped(
+        &self,
+        ctx: &ExecutionContext,
+        stdin: StdinSource,
+        #[cfg(unix)] jobserver: Option<&crate::jobserver::Jobserver>,
+    ) -> Result<(
+        tokio::process::Child,
+        JoinHandle<Vec<u8>>,
+        JoinHandle<Vec<u8>>,
+        Option<JoinHandle<()>>,
+    )> {
+        let mut cmd = self.strategy.build_command(ctx)?;
+        // Scope the jobserver fds to exactly this child (see
+        // `Jobserver::keep_open_across_exec`); every other process this
+        // launcher spawns leaves them `FD_CLOEXEC` and never sees them.
+        #[cfg(unix)]
+        if let Some(js) = jobserver {
+            js.keep_open_across_exec(&mut cmd);
+        }
+        let mut command = tokio::process::Command::from(cmd);
+        command
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
-            .stdin(Stdio::null())
-            .kill_on_drop(true) // Ensure child is killed if the engine panics/drops
+            .kill_on_drop(true); // Ensure child is killed if the engine panics/drops
+        match &stdin {
+            StdinSource::Null => {
+                command.stdin(Stdio::null());
+            }
+            StdinSource::Inherit => {
+                command.stdin(Stdio::inherit());
+            }
+            StdinSource::Bytes(_) | StdinSource::Reader(_) => {
+                command.stdin(Stdio::piped());
+            }
+        }
+
+        #[cfg(unix)]
+        if ctx.kill_process_group {
+            use tokio::process::CommandExt;
+            command.process_group(0);
+        }
+
+        let mut child = command
             .spawn()
             .map_err(|e| anyhow!("Spawn failed: {}", e))?;
 
+        let stdin_task = match stdin {
+            StdinSource::Null | StdinSource::Inherit => None,
+            StdinSource::Bytes(bytes) => {
+                let mut child_stdin = child.stdin.take().expect("No stdin");
+                Some(tokio::spawn(async move {
+                    use tokio::io::AsyncWriteExt;
+                    let _ = child_stdin.write_all(&bytes).await;
+                    let _ = child_stdin.shutdown().await;
+                }))
+            }
+            StdinSource::Reader(mut reader) => {
+                let mut child_stdin = child.stdin.take().expect("No stdin");
+                Some(tokio::spawn(async move {
+                    use tokio::io::AsyncWriteExt;
+                    let _ = tokio::io::copy(&mut reader, &mut child_stdin).await;
+                    let _ = child_stdin.shutdown().await;
+                }))
+            }
+        };
+
         let mut child_stdout = child.stdout.take().expect("No stdout");
         let mut child_stderr = child.stderr.take().expect("No stderr");
 
-        // Task 1: Pump STDOUT to parent's stdout
+        let id = ctx.id.clone();
+        let events = self.events.clone();
+        let mode = ctx.output_mode;
         let stdout_task = tokio::spawn(async move {
-            let mut stdout = tokio::io::stdout();
-            let _ = tokio::io::copy(&mut child_stdout, &mut stdout).await;
+            pump_output(&mut child_stdout, &events, &id, true, mode).await
         });
 
-        // Task 2: Pump STDERR to parent's stderr
+        let id = ctx.id.clone();
+        let events = self.events.clone();
+        let mode = ctx.output_mode;
         let stderr_task = tokio::spawn(async move {
-            let mut stderr = tokio::io::stderr();
-            let _ = tokio::io::copy(&mut child_stderr, &mut stderr).await;
+            pump_output(&mut child_stderr, &events, &id, false, mode).await
         });
 
-        // Task 3: Watch for cancellation signals
-        let kill_task = tokio::spawn(async move {
-            wait_for_termination().await;
-        });
+        Ok((child, stdout_task, stderr_task, stdin_task))
+    }
+
+    /// Runs an ordered list of pipeline steps against a single constructed
+    /// `IsolationStrategy`, short-circuiting on the first step whose exit
+    /// code is nonzero unless that step set `continue_on_error`. The overall
+    /// return value is the exit code of the first failing step, or `0` if
+    /// every step succeeded.
+    ///
+    /// `base_ctx` supplies everything steps don't override themselves
+    /// (workspace root, network policy, jobserver settings, helper binary
+    /// paths, ...). Note each step still spawns its own sandboxed process —
+    /// sharing the `IsolationStrategy` only avoids repeating OS/strategy
+    /// selection, it doesn't keep a single sandbox namespace alive across steps.
+    pub async fn run_pipeline(
+        &self,
+        base_ctx: ExecutionContext,
+        steps: Vec<crate::pipeline::PipelineStep>,
+    ) -> Result<i32> {
+        let total = steps.len();
+        let mut overall_code = 0;
+
+        for (i, step) in steps.into_iter().enumerate() {
+            eprintln!(
+                "[Launcher] Pipeline step {}/{total}: {} {:?}",
+                i + 1,
+                step.cmd,
+                step.args
+            );
 
-        // Main Loop: Wait for process exit OR cancellation
-        let exit_status = tokio::select! {
-            status = child.wait() => status,
-            _ = kill_task => {
-                let _ = child.kill().await;
-                // Return -1 to indicate cancellation
-                return Ok(-1);
+            let mut ctx = base_ctx.clone();
+            ctx.cmd = step.cmd;
+            ctx.args = step.args;
+            if step.cwd.is_some() {
+                ctx.cwd = step.cwd;
             }
+            for (key, val) in step.env {
+                ctx.env_vars.insert(key, val);
+            }
+
+            let code = self.run(ctx).await?.code;
+            eprintln!("[Launcher] Pipeline step {}/{total} exited with code {code}", i + 1);
+
+            if code != 0 {
+                overall_code = first_failure_code(overall_code, code);
+                if !step.continue_on_error {
+                    eprintln!("[Launcher] Step {}/{total} failed, stopping pipeline", i + 1);
+                    break;
+                }
+            }
+        }
+
+        Ok(overall_code)
+    }
+
+    /// Runs `ctx`'s command under `notify`-driven restarts: whenever
+    /// `opts.watch_path` (or `ctx.root_path` by default) changes on disk, the
+    /// current child is gracefully terminated and respawned via the same
+    /// `IsolationStrategy`. Returns the exit code of the last run once a
+    /// top-level termination signal arrives.
+    ///
+    /// The watcher observes the host-side path rather than anything inside
+    /// the sandbox/namespace, since that's the only view of the workspace
+    /// that exists outside the child.
+    pub async fn watch(&self, ctx: ExecutionContext, opts: WatchOptions) -> Result<i32> {
+        use notify::{RecursiveMode, Watcher};
+
+        let mut ctx = ctx;
+
+        // Stood up once for the whole watch session rather than per restart,
+        // so the jobserver's token pool bounds total build parallelism
+        // across every spawn this session makes, the same way it bounds a
+        // single `run()` call's.
+        #[cfg(unix)]
+        let jobserver = match ctx.jobs {
+            Some(n) if n >= 1 => {
+                let js = crate::jobserver::Jobserver::new(n)?;
+                let auth = js.makeflags_value();
+                ctx.env_vars.insert("MAKEFLAGS".to_string(), auth.clone());
+                ctx.env_vars.insert("CARGO_MAKEFLAGS".to_string(), auth);
+                Some(js)
+            }
+            _ => None,
         };
 
-        // CRITICAL: Wait for IO tasks to finish flushing buffers before exiting.
-        // This prevents race conditions where the process exits but data is still in the pipe.
-        let _ = tokio::join!(stdout_task, stderr_task);
+        let watch_path = opts.watch_path.clone().unwrap_or_else(|| ctx.root_path.clone());
 
-        match exit_status {
-            Ok(s) => Ok(s.code().unwrap_or(-1)),
-            Err(e) => Err(anyhow!("Wait failed: {}", e)),
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if let Ok(event) = res {
+                let _ = tx.send(event);
+            }
+        })
+        .context("failed to create filesystem watcher")?;
+        watcher
+            .watch(Path::new(&watch_path), RecursiveMode::Recursive)
+            .with_context(|| format!("failed to watch `{watch_path}`"))?;
+
+        eprintln!("[Launcher] Watching `{watch_path}` for changes (on-busy: {:?})", opts.on_busy);
+
+        // Emitted once: the strategy doesn't change across restarts, unlike
+        // the pid/exit code below, which are per-spawn.
+        self.events.emit(
+            &ctx.id,
+            EventKind::StrategySelected {
+                strategy: self.strategy.name().to_string(),
+            },
+        );
+
+        let mut last_code = 0;
+        loop {
+            eprintln!("[Launcher] Starting: {} {:?}", ctx.cmd, ctx.args);
+            #[cfg(unix)]
+            let (mut child, stdout_task, stderr_task, stdin_task) =
+                self.spawn_piped(&ctx, ctx.stdin.clone(), jobserver.as_ref())?;
+            #[cfg(not(unix))]
+            let (mut child, stdout_task, stderr_task, stdin_task) =
+                self.spawn_piped(&ctx, ctx.stdin.clone())?;
+            #[cfg(windows)]
+            let windows_job = self.strategy.assign_job(&child);
+            if let Some(pid) = child.id() {
+                self.events.emit(&ctx.id, EventKind::ProcessStarted { pid });
+            }
+
+            enum Event {
+                Exited(std::io::Result<std::process::ExitStatus>),
+                Changed,
+                Terminated,
+            }
+
+            let event = tokio::select! {
+                status = wait_child(&mut child) => Event::Exited(status),
+                Some(_) = debounced_change(&mut rx, opts.debounce) => Event::Changed,
+                _ = wait_for_termination() => Event::Terminated,
+            };
+
+            match event {
+                Event::Exited(status) => {
+                    let _ = tokio::join!(stdout_task, stderr_task);
+                    if let Some(t) = stdin_task {
+                        let _ = t.await;
+                    }
+                    last_code = match status {
+                        Ok(s) => {
+                            let (code, signal) = exit_event_fields(&s);
+                            self.events
+                                .emit(&ctx.id, EventKind::ProcessExited { code, signal });
+                            code.unwrap_or(-1)
+                        }
+                        Err(e) => return Err(anyhow!("Wait failed: {}", e)),
+                    };
+                    eprintln!("[Launcher] Command exited with {last_code}; waiting for changes...");
+                    tokio::select! {
+                        Some(_) = debounced_change(&mut rx, opts.debounce) => {}
+                        _ = wait_for_termination() => return Ok(last_code),
+                    }
+                }
+                Event::Changed => match opts.on_busy {
+                    OnBusyPolicy::Restart => {
+                        eprintln!("[Launcher] Change detected under `{watch_path}`, restarting...");
+                        let outcome = graceful_terminate(
+                            &mut child,
+                            self.grace_period,
+                            ctx.kill_process_group,
+                            #[cfg(windows)]
+                            windows_job.as_ref(),
+                        )
+                        .await;
+                        let _ = tokio::join!(stdout_task, stderr_task);
+                        if let Some(t) = stdin_task {
+                            let _ = t.await;
+                        }
+                        self.events.emit(
+                            &ctx.id,
+                            EventKind::ProcessExited {
+                                code: Some(outcome.exit_code()),
+                                signal: None,
+                            },
+                        );
+                    }
+                    OnBusyPolicy::Signal => {
+                        eprintln!("[Launcher] Change detected under `{watch_path}`, signaling child...");
+                        #[cfg(unix)]
+                        if let Some(pid) = child.id() {
+                            let target = if ctx.kill_process_group {
+                                -(pid as libc::pid_t)
+                            } else {
+                                pid as libc::pid_t
+                            };
+                            // SAFETY: `pid` is the live child we just spawned.
+                            unsafe {
+                                libc::kill(target, libc::SIGUSR1);
+                            }
+                            self.events.emit(
+                                &ctx.id,
+                                EventKind::SignalForwarded {
+                                    signal: signal_name(libc::SIGUSR1).to_string(),
+                                    pgid: target,
+                                },
+                            );
+                        }
+                        // Not a restart: let the (now-signaled) child keep running.
+                        let status = tokio::select! {
+                            status = wait_child(&mut child) => status,
+                            _ = wait_for_termination() => {
+                                self.emit_termination_signal(&ctx.id);
+                                let outcome = graceful_terminate(
+                                    &mut child,
+                                    self.grace_period,
+                                    ctx.kill_process_group,
+                                    #[cfg(windows)]
+                                    windows_job.as_ref(),
+                                )
+                                .await;
+                                let _ = tokio::join!(stdout_task, stderr_task);
+                                if let Some(t) = stdin_task {
+                                    let _ = t.await;
+                                }
+                                let code = outcome.exit_code();
+                                self.events.emit(
+                                    &ctx.id,
+                                    EventKind::ProcessExited { code: Some(code), signal: None },
+                                );
+                                return Ok(code);
+                            }
+                        };
+                        let _ = tokio::join!(stdout_task, stderr_task);
+                        if let Some(t) = stdin_task {
+                            let _ = t.await;
+                        }
+                        last_code = match status {
+                            Ok(s) => {
+                                let (code, signal) = exit_event_fields(&s);
+                                self.events
+                                    .emit(&ctx.id, EventKind::ProcessExited { code, signal });
+                                code.unwrap_or(-1)
+                            }
+                            Err(e) => return Err(anyhow!("Wait failed: {}", e)),
+                        };
+                    }
+                    OnBusyPolicy::Queue => {
+                        eprintln!(
+                            "[Launcher] Change detected under `{watch_path}`, queued until the \
+                             current run exits..."
+                        );
+                        let status = tokio::select! {
+                            status = wait_child(&mut child) => status,
+                            _ = wait_for_termination() => {
+                                self.emit_termination_signal(&ctx.id);
+                                let outcome = graceful_terminate(
+                                    &mut child,
+                                    self.grace_period,
+                                    ctx.kill_process_group,
+                                    #[cfg(windows)]
+                                    windows_job.as_ref(),
+                                )
+                                .await;
+                                let _ = tokio::join!(stdout_task, stderr_task);
+                                if let Some(t) = stdin_task {
+                                    let _ = t.await;
+                                }
+                                let code = outcome.exit_code();
+                                self.events.emit(
+                                    &ctx.id,
+                                    EventKind::ProcessExited { code: Some(code), signal: None },
+                                );
+                                return Ok(code);
+                            }
+                        };
+                        let _ = tokio::join!(stdout_task, stderr_task);
+                        if let Some(t) = stdin_task {
+                            let _ = t.await;
+                        }
+                        match status {
+                            Ok(s) => {
+                                let (code, signal) = exit_event_fields(&s);
+                                self.events
+                                    .emit(&ctx.id, EventKind::ProcessExited { code, signal });
+                                continue;
+                            }
+                            Err(e) => return Err(anyhow!("Wait failed: {}", e)),
+                        }
+                    }
+                },
+                Event::Terminated => {
+                    self.emit_termination_signal(&ctx.id);
+                    let outcome = graceful_terminate(
+                        &mut child,
+                        self.grace_period,
+                        ctx.kill_process_group,
+                        #[cfg(windows)]
+                        windows_job.as_ref(),
+                    )
+                    .await;
+                    let _ = tokio::join!(stdout_task, stderr_task);
+                    if let Some(t) = stdin_task {
+                        let _ = t.await;
+                    }
+                    let code = outcome.exit_code();
+                    self.events.emit(
+                        &ctx.id,
+                        EventKind::ProcessExited { code: Some(code), signal: None },
+                    );
+                    return Ok(code);
+                }
+            }
+        }
+    }
+
+    /// Emits `SignalReceived` for a top-level termination request observed
+    /// via `wait_for_termination`. That helper collapses SIGTERM/SIGINT
+    /// (Ctrl-C on Windows) into a single wake-up without saying which one
+    /// fired, so this reports the platform's primary signal rather than the
+    /// literal one received.
+    fn emit_termination_signal(&self, id: &str) {
+        #[cfg(unix)]
+        let signal = "SIGTERM";
+        #[cfg(windows)]
+        let signal = "CTRL_C";
+        self.events
+            .emit(id, EventKind::SignalReceived { signal: signal.to_string() });
+    }
+}
+
+/// Folds a failing step's exit code into `overall`, keeping whichever code
+/// was seen first. `step_code` must be nonzero; `overall` should be `0` until
+/// the first failure.
+fn first_failure_code(overall: i32, step_code: i32) -> i32 {
+    if overall == 0 {
+        step_code
+    } else {
+        overall
+    }
+}
+
+/// Splits `status` into the `code`/`signal` pair `EventKind::ProcessExited`
+/// reports; shared by `run()` and `watch()` for their exit events.
+fn exit_event_fields(status: &std::process::ExitStatus) -> (Option<i32>, Option<i32>) {
+    let code = status.code();
+    #[cfg(unix)]
+    let signal = {
+        use std::os::unix::process::ExitStatusExt;
+        status.signal()
+    };
+    #[cfg(not(unix))]
+    let signal = None;
+    (code, signal)
+}
+
+/// Waits for `child` to exit, preferring the pidfd-based backend on Linux
+/// (falling back to the default `child.wait()` signal-driven path if the
+/// kernel doesn't support `pidfd_open` or the pidfd can't be opened) so
+/// wait notification stays local to this specific child rather than racing
+/// an embedding application's own SIGCHLD handling.
+#[cfg(target_os = "linux")]
+async fn wait_child(
+    child: &mut tokio::process::Child,
+) -> std::io::Result<std::process::ExitStatus> {
+    let pidfd = child.id().and_then(|pid| crate::pidfd::PidFd::open(pid).ok().flatten());
+    let Some(pidfd) = pidfd else {
+        return child.wait().await;
+    };
+    pidfd.exited().await;
+    // The pidfd only signals readiness; the exit status still has to be
+    // collected through the normal path. The process has already exited by
+    // this point, so this resolves immediately.
+    match child.try_wait()? {
+        Some(status) => Ok(status),
+        None => child.wait().await,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+async fn wait_child(
+    child: &mut tokio::process::Child,
+) -> std::io::Result<std::process::ExitStatus> {
+    child.wait().await
+}
+
+/// Drains change events arriving within `debounce` of the first one so a
+/// burst of saves collapses into a single restart.
+async fn debounced_change(
+    rx: &mut tokio::sync::mpsc::UnboundedReceiver<notify::Event>,
+    debounce: std::time::Duration,
+) -> Option<()> {
+    rx.recv().await?;
+    let _ = tokio::time::timeout(debounce, async { while rx.recv().await.is_some() {} }).await;
+    Some(())
+}
+
+/// Whether a terminated child exited on its own after being asked nicely, or
+/// had to be force-killed once `grace_period` elapsed without a response.
+enum TerminationOutcome {
+    /// The child exited (with this status) before the grace period elapsed.
+    Graceful(std::process::ExitStatus),
+    /// The grace period elapsed and the child had to be force-killed.
+    ForceKilled,
+}
+
+/// Exit code reported for [`TerminationOutcome::ForceKilled`], distinguishing
+/// it from a child that exited gracefully with an ordinary code.
+const FORCE_KILLED_EXIT_CODE: i32 = -9;
+
+impl TerminationOutcome {
+    /// The exit code `watch` reports for this outcome.
+    fn exit_code(&self) -> i32 {
+        match self {
+            TerminationOutcome::Graceful(status) => status.code().unwrap_or(-1),
+            TerminationOutcome::ForceKilled => FORCE_KILLED_EXIT_CODE,
         }
     }
 }
 
+/// SIGTERM, then a hard kill after `grace_period` if the child hasn't exited
+/// on its own. When `kill_process_group` is set, both the SIGTERM and the
+/// eventual SIGKILL target the child's whole process group instead of just
+/// the directly-spawned process, so descendants it forks don't leak. On
+/// Windows the nearest equivalent is used: `CTRL_BREAK_EVENT` first, which
+/// `WindowsJobStrategy` sets the child up to receive on its own via
+/// `CREATE_NEW_PROCESS_GROUP` without also hitting the launcher's own
+/// console; if that doesn't land in time, `job` (see
+/// `IsolationStrategy::assign_job`) is torn down with `TerminateJobObject`
+/// instead of killing just `child`, so descendants it forked don't leak.
+async fn graceful_terminate(
+    child: &mut tokio::process::Child,
+    grace_period: std::time::Duration,
+    kill_process_group: bool,
+    #[cfg(windows)] job: Option<&WindowsJob>,
+) -> TerminationOutcome {
+    #[cfg(unix)]
+    if let Some(pid) = child.id() {
+        let target = if kill_process_group {
+            -(pid as libc::pid_t)
+        } else {
+            pid as libc::pid_t
+        };
+        // SAFETY: `target` is either this child's own pid or its own process
+        // group (negated), both valid kill targets here.
+        unsafe {
+            libc::kill(target, libc::SIGTERM);
+        }
+        if let Ok(Ok(status)) = tokio::time::timeout(grace_period, child.wait()).await {
+            return TerminationOutcome::Graceful(status);
+        }
+        // SAFETY: see above.
+        unsafe {
+            libc::kill(target, libc::SIGKILL);
+        }
+        let _ = child.wait().await;
+        return TerminationOutcome::ForceKilled;
+    }
+    #[cfg(windows)]
+    if let Some(pid) = child.id() {
+        // SAFETY: `pid` is the id of a child this process just spawned with
+        // `CREATE_NEW_PROCESS_GROUP` (see `WindowsJobStrategy`), which makes
+        // it that process group's id too — a valid target for
+        // `GenerateConsoleCtrlEvent`. Ignored on failure: the grace-period
+        // wait below still falls through to a hard kill either way.
+        unsafe {
+            let _ = windows::Win32::System::Console::GenerateConsoleCtrlEvent(
+                windows::Win32::System::Console::CTRL_BREAK_EVENT,
+                pid,
+            );
+        }
+        if let Ok(Ok(status)) = tokio::time::timeout(grace_period, child.wait()).await {
+            return TerminationOutcome::Graceful(status);
+        }
+    }
+    #[cfg(windows)]
+    if let Some(job) = job {
+        job.terminate();
+        let _ = child.wait().await;
+        return TerminationOutcome::ForceKilled;
+    }
+    let _ = child.kill().await;
+    TerminationOutcome::ForceKilled
+}
+
 /// Cross-platform signal listener for graceful shutdown.
-async fn wait_for_termination() {
+pub(crate) async fn wait_for_termination() {
     #[cfg(unix)]
     {
         let mut sigterm = signal(SignalKind::terminate()).unwrap();
@@ -126,3 +905,206 @@ async fn wait_for_termination() {
         let _ = signal::ctrl_c().await;
     }
 }
+
+#[cfg(unix)]
+fn signal_name(sig: i32) -> &'static str {
+    match sig {
+        libc::SIGTERM => "SIGTERM",
+        libc::SIGINT => "SIGINT",
+        libc::SIGHUP => "SIGHUP",
+        libc::SIGQUIT => "SIGQUIT",
+        libc::SIGUSR1 => "SIGUSR1",
+        libc::SIGUSR2 => "SIGUSR2",
+        libc::SIGKILL => "SIGKILL",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Waits for the child to exit while forwarding every signal the launcher
+/// receives to it. When `kill_process_group` is set (the default), signals
+/// target the child's whole process group instead of just the
+/// directly-spawned process, so descendants the sandboxed command spawns
+/// (bwrap, a shell wrapper, etc.) see them too.
+///
+/// SIGTERM/SIGINT/SIGHUP/SIGQUIT trigger the graceful shutdown ladder: the
+/// signal is forwarded first, then if the target hasn't exited within
+/// `shutdown_timeout` it's escalated to SIGKILL. SIGUSR1/SIGUSR2 are simply
+/// forwarded and otherwise don't affect the wait.
+///
+/// Returns a [`TerminationOutcome`] rather than a plain exit status so a
+/// child that had to be SIGKILLed after `shutdown_timeout` is distinguishable
+/// from one that exited on its own, the same distinction `graceful_terminate`
+/// already reports for watch-mode restarts.
+#[cfg(unix)]
+async fn wait_with_signal_forwarding(
+    child: &mut tokio::process::Child,
+    pid: i32,
+    kill_process_group: bool,
+    shutdown_timeout: std::time::Duration,
+    events: &EventSink,
+    id: &str,
+) -> std::io::Result<TerminationOutcome> {
+    // When `kill_process_group` is set, `pid` is also the pgid (the child is
+    // its own group leader via `process_group(0)` in `spawn_piped`), so the
+    // negated pid targets the whole group instead of just this one process.
+    let target = if kill_process_group { -pid } else { pid };
+
+    let mut sigterm = signal(SignalKind::terminate()).unwrap();
+    let mut sigint = signal(SignalKind::interrupt()).unwrap();
+    let mut sighup = signal(SignalKind::hangup()).unwrap();
+    let mut sigquit = signal(SignalKind::quit()).unwrap();
+    let mut sigusr1 = signal(SignalKind::user_defined1()).unwrap();
+    let mut sigusr2 = signal(SignalKind::user_defined2()).unwrap();
+
+    loop {
+        let sig = tokio::select! {
+            status = wait_child(child) => return status.map(TerminationOutcome::Graceful),
+            _ = sigterm.recv() => libc::SIGTERM,
+            _ = sigint.recv() => libc::SIGINT,
+            _ = sighup.recv() => libc::SIGHUP,
+            _ = sigquit.recv() => libc::SIGQUIT,
+            _ = sigusr1.recv() => libc::SIGUSR1,
+            _ = sigusr2.recv() => libc::SIGUSR2,
+        };
+
+        events.emit(
+            id,
+            EventKind::SignalReceived {
+                signal: signal_name(sig).to_string(),
+            },
+        );
+        eprintln!("[Launcher] Received signal {sig}, forwarding to pid {target}");
+        // SAFETY: `target` is either this child's own pid or its own process
+        // group (negated), both of which are valid kill targets here.
+        unsafe {
+            libc::kill(target, sig);
+        }
+        events.emit(
+            id,
+            EventKind::SignalForwarded {
+                signal: signal_name(sig).to_string(),
+                pgid: target,
+            },
+        );
+
+        let is_terminal = matches!(
+            sig,
+            libc::SIGTERM | libc::SIGINT | libc::SIGHUP | libc::SIGQUIT
+        );
+        if !is_terminal {
+            continue;
+        }
+
+        match tokio::time::timeout(shutdown_timeout, child.wait()).await {
+            Ok(status) => return status.map(TerminationOutcome::Graceful),
+            Err(_elapsed) => {
+                eprintln!("[Launcher] Grace period elapsed, sending SIGKILL to pid {target}");
+                // SAFETY: see above.
+                unsafe {
+                    libc::kill(target, libc::SIGKILL);
+                }
+                events.emit(
+                    id,
+                    EventKind::SignalForwarded {
+                        signal: signal_name(libc::SIGKILL).to_string(),
+                        pgid: target,
+                    },
+                );
+                let _ = child.wait().await;
+                return Ok(TerminationOutcome::ForceKilled);
+            }
+        }
+    }
+}
+
+#[cfg(windows)]
+async fn wait_with_signal_forwarding(
+    child: &mut tokio::process::Child,
+    _shutdown_timeout: std::time::Duration,
+    events: &EventSink,
+    id: &str,
+    job: Option<&WindowsJob>,
+) -> std::io::Result<TerminationOutcome> {
+    tokio::select! {
+        status = child.wait() => status.map(TerminationOutcome::Graceful),
+        _ = signal::ctrl_c() => {
+            events.emit(id, EventKind::SignalReceived { signal: "CTRL_C".to_string() });
+            // `job` (see `IsolationStrategy::assign_job`) covers the
+            // sandboxed child plus anything it forked; `TerminateJobObject`
+            // on it tears down the whole tree instead of just `child`.
+            match job {
+                Some(job) => job.terminate(),
+                None => { let _ = child.kill().await; }
+            }
+            let _ = child.wait().await;
+            Ok(TerminationOutcome::ForceKilled)
+        }
+    }
+}
+
+/// Pumps `src` according to `mode`: `Inherit`/`Tee` stream it to the
+/// launcher's own stdout/stderr, `Capture`/`Tee` accumulate it into the
+/// returned buffer (empty under `Inherit`). Emits `StdoutBytes`/`StderrBytes`
+/// events as each chunk is read so a supervisor parsing `--events-fd`/
+/// `--events-file` can track I/O volume without buffering the stream itself.
+async fn pump_output<R>(
+    src: &mut R,
+    events: &EventSink,
+    id: &str,
+    is_stdout: bool,
+    mode: OutputMode,
+) -> Vec<u8>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let mut captured = Vec::new();
+    let mut buf = [0u8; 8192];
+    loop {
+        let n = match src.read(&mut buf).await {
+            Ok(0) | Err(_) => return captured,
+            Ok(n) => n,
+        };
+        let chunk = &buf[..n];
+
+        if matches!(mode, OutputMode::Inherit | OutputMode::Tee) {
+            let wrote = if is_stdout {
+                tokio::io::stdout().write_all(chunk).await
+            } else {
+                tokio::io::stderr().write_all(chunk).await
+            };
+            if wrote.is_err() {
+                return captured;
+            }
+        }
+        if matches!(mode, OutputMode::Capture | OutputMode::Tee) {
+            captured.extend_from_slice(chunk);
+        }
+
+        let kind = if is_stdout {
+            EventKind::StdoutBytes { bytes: n as u64 }
+        } else {
+            EventKind::StderrBytes { bytes: n as u64 }
+        };
+        events.emit(id, kind);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_failure_code_keeps_earliest_nonzero() {
+        let mut overall = 0;
+        overall = first_failure_code(overall, 3);
+        overall = first_failure_code(overall, 7);
+        assert_eq!(overall, 3);
+    }
+
+    #[test]
+    fn first_failure_code_passes_through_when_no_prior_failure() {
+        assert_eq!(first_failure_code(0, 5), 5);
+    }
+}
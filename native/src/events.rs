@@ -0,0 +1,95 @@
+//! Structured NDJSON event stream for process lifecycle and I/O accounting.
+//!
+//! Lets a supervising process (the Dart `workspace_sandbox` layer, or any
+//! other caller) parse machine-readable telemetry instead of scraping the
+//! human-readable `eprintln!` lines the launcher also prints.
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+/// One lifecycle/IO event, tagged with the execution id before being written.
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum EventKind {
+    StrategySelected { strategy: String },
+    ProcessStarted { pid: u32 },
+    StdoutBytes { bytes: u64 },
+    StderrBytes { bytes: u64 },
+    SignalReceived { signal: String },
+    SignalForwarded { signal: String, pgid: i32 },
+    ProcessExited { code: Option<i32>, signal: Option<i32> },
+}
+
+struct Inner {
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+/// A sink for NDJSON events, cheap to clone so IO-pump tasks and signal
+/// handlers can each hold their own handle. `EventSink::disabled()` is a
+/// no-op sink, so callers don't need to branch on whether `--events-fd`/
+/// `--events-file` was passed.
+#[derive(Clone)]
+pub struct EventSink {
+    inner: Option<Arc<Inner>>,
+}
+
+impl EventSink {
+    /// A sink that drops every event; the default when no events output was requested.
+    pub fn disabled() -> Self {
+        EventSink { inner: None }
+    }
+
+    /// Opens `fd` (already owned by this process) as the event stream.
+    #[cfg(unix)]
+    pub fn from_fd(fd: i32) -> Result<Self> {
+        use std::os::unix::io::FromRawFd;
+        // SAFETY: `fd` is expected to be a valid, process-owned fd passed via
+        // `--events-fd`; ownership transfers to the `File` we construct here.
+        let file = unsafe { std::fs::File::from_raw_fd(fd) };
+        Ok(EventSink {
+            inner: Some(Arc::new(Inner {
+                writer: Mutex::new(Box::new(file)),
+            })),
+        })
+    }
+
+    /// Opens (creating/appending) `path` as the event stream.
+    pub fn from_path(path: &str) -> Result<Self> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("failed to open events file `{path}`"))?;
+        Ok(EventSink {
+            inner: Some(Arc::new(Inner {
+                writer: Mutex::new(Box::new(file)),
+            })),
+        })
+    }
+
+    /// Serializes `kind` as one NDJSON line tagged with `id` and writes it.
+    /// Silently drops the event on a disabled sink or a write/serialize error
+    /// — telemetry must never be allowed to fail the actual run.
+    pub fn emit(&self, id: &str, kind: EventKind) {
+        let Some(inner) = &self.inner else {
+            return;
+        };
+        let Ok(mut value) = serde_json::to_value(&kind) else {
+            return;
+        };
+        if let serde_json::Value::Object(map) = &mut value {
+            map.insert("id".to_string(), serde_json::Value::String(id.to_string()));
+        }
+        let Ok(mut line) = serde_json::to_string(&value) else {
+            return;
+        };
+        line.push('\n');
+
+        if let Ok(mut writer) = inner.writer.lock() {
+            let _ = writer.write_all(line.as_bytes());
+            let _ = writer.flush();
+        }
+    }
+}
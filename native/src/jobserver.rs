@@ -0,0 +1,117 @@
+//! GNU Make jobserver implementation.
+//!
+//! When the launcher is asked to bound total build parallelism, it creates the
+//! read/write ends of the jobserver pipe itself and hands child tools the
+//! `MAKEFLAGS`/`CARGO_MAKEFLAGS` auth string so `make`, `cargo`, and friends
+//! coordinate through it instead of oversubscribing the host.
+
+use anyhow::{Context, Result};
+use std::os::unix::io::RawFd;
+use std::process::Command;
+
+/// Owns the jobserver pipe for the lifetime of a single `Engine::run` call
+/// (or of a whole `Engine::watch` session, shared across its restarts).
+///
+/// `jobs - 1` single-byte tokens are pushed into the pipe up front; the
+/// implicit token represents the child process itself. Both fds are created
+/// with `FD_CLOEXEC` set, so by default nothing spawned while this
+/// `Jobserver` is alive inherits them — `keep_open_across_exec` opts a single
+/// command's child back in, scoping the fds to exactly the one process
+/// that's supposed to see them.
+pub struct Jobserver {
+    read_fd: RawFd,
+    write_fd: RawFd,
+}
+
+impl Jobserver {
+    /// Creates the pipe and pre-loads it with `jobs.saturating_sub(1)` tokens.
+    pub fn new(jobs: usize) -> Result<Self> {
+        let mut fds = [0 as RawFd; 2];
+        // SAFETY: `fds` is a valid, appropriately-sized out-param for
+        // `pipe2`. `O_CLOEXEC` keeps both ends from leaking into any child
+        // this process spawns until `keep_open_across_exec` explicitly
+        // clears it for one of them.
+        let rc = unsafe { libc::pipe2(fds.as_mut_ptr(), libc::O_CLOEXEC) };
+        if rc != 0 {
+            return Err(anyhow::Error::from(std::io::Error::last_os_error()))
+                .context("failed to create jobserver pipe");
+        }
+        let (read_fd, write_fd) = (fds[0], fds[1]);
+
+        let tokens = jobs.saturating_sub(1);
+        if tokens > 0 {
+            let buf = vec![b'+'; tokens];
+            // SAFETY: `write_fd` is a freshly created, valid pipe fd and `buf`
+            // is a live slice for the duration of the call.
+            let written = unsafe { libc::write(write_fd, buf.as_ptr().cast(), buf.len()) };
+            if written < 0 || written as usize != buf.len() {
+                // SAFETY: both fds were just created by us above.
+                unsafe {
+                    libc::close(read_fd);
+                    libc::close(write_fd);
+                }
+                return Err(anyhow::Error::from(std::io::Error::last_os_error()))
+                    .context("failed to pre-load jobserver tokens");
+            }
+        }
+
+        Ok(Jobserver { read_fd, write_fd })
+    }
+
+    /// Renders the `--jobserver-auth=R,W` value shared by `MAKEFLAGS` and
+    /// `CARGO_MAKEFLAGS`.
+    pub fn makeflags_value(&self) -> String {
+        format!("--jobserver-auth={},{}", self.read_fd, self.write_fd)
+    }
+
+    /// Arranges for `command`'s child — and only that child — to inherit
+    /// this jobserver's fds across `exec`, by clearing `FD_CLOEXEC` on both
+    /// of them in a `pre_exec` hook that only runs in that one forked child.
+    /// Any other process this launcher spawns while the `Jobserver` is alive
+    /// never sees these fds, since they're created with `FD_CLOEXEC` set.
+    pub fn keep_open_across_exec(&self, command: &mut Command) {
+        use std::os::unix::process::CommandExt;
+        let (read_fd, write_fd) = (self.read_fd, self.write_fd);
+        // SAFETY: runs after `fork` and before `exec` in the child process
+        // only; it touches just the two fds this struct owns and does
+        // nothing else unsafe for that narrow window.
+        unsafe {
+            command.pre_exec(move || {
+                clear_cloexec(read_fd)?;
+                clear_cloexec(write_fd)?;
+                Ok(())
+            });
+        }
+    }
+}
+
+/// Clears `FD_CLOEXEC` on `fd` so it survives the child's upcoming `exec`.
+fn clear_cloexec(fd: RawFd) -> std::io::Result<()> {
+    // SAFETY: `fd` is a valid, open fd for the duration of this call (it's
+    // only ever invoked from within `keep_open_across_exec`'s `pre_exec`
+    // hook, before the fds are closed).
+    unsafe {
+        let flags = libc::fcntl(fd, libc::F_GETFD);
+        if flags < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+        if libc::fcntl(fd, libc::F_SETFD, flags & !libc::FD_CLOEXEC) < 0 {
+            return Err(std::io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+impl Drop for Jobserver {
+    fn drop(&mut self) {
+        // Teardown is just closing both ends; tokens still in the pipe are
+        // not drained, and go away with the pipe itself once no other
+        // process holds either end.
+        // SAFETY: both fds were opened by `Jobserver::new` and are owned
+        // exclusively by this struct.
+        unsafe {
+            libc::close(self.read_fd);
+            libc::close(self.write_fd);
+        }
+    }
+}
@@ -8,12 +8,23 @@
 #![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
 
 mod engine;
+mod events;
+mod jobserver;
+mod manager;
+#[cfg(target_os = "linux")]
+mod pidfd;
+mod pipeline;
 mod strategies;
+mod watch;
 
 use crate::engine::Engine;
-use crate::strategies::base::ExecutionContext;
+use crate::events::EventSink;
+use crate::pipeline::PipelineSpec;
+use crate::strategies::base::{ExecutionContext, OutputMode, StdinSource};
+use crate::watch::{OnBusyPolicy, WatchOptions};
 use clap::Parser;
 use std::process;
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(
@@ -41,6 +52,81 @@ struct Args {
     #[arg(long, value_parser = parse_key_val)]
     env: Vec<(String, String)>,
 
+    /// Total build parallelism to share across sandboxed build tools via a
+    /// GNU Make jobserver (e.g. `cargo`, `make`, `ninja`).
+    #[arg(long)]
+    jobs: Option<usize>,
+
+    /// Override for the `sandbox-exec` binary (macOS).
+    #[arg(long, env = "WORKSPACE_SANDBOX_SANDBOX_EXEC_PATH")]
+    sandbox_exec_path: Option<String>,
+
+    /// Override for the `bwrap` binary (Linux).
+    #[arg(long, env = "WORKSPACE_SANDBOX_BWRAP_PATH")]
+    bwrap_path: Option<String>,
+
+    /// Override for the fallback shell used to run shell builtins.
+    #[arg(long, env = "WORKSPACE_SANDBOX_SHELL_PATH")]
+    shell_path: Option<String>,
+
+    /// Re-run the command whenever the workspace changes on disk instead of
+    /// exiting after the first run.
+    #[arg(long)]
+    watch: bool,
+
+    /// Path to watch recursively. Defaults to `--workspace`.
+    #[arg(long)]
+    watch_path: Option<String>,
+
+    /// How long to keep collapsing further change events after the first one
+    /// before restarting.
+    #[arg(long, default_value_t = 200)]
+    debounce_ms: u64,
+
+    /// What to do with a filesystem change that arrives while the command is
+    /// still running: `restart`, `signal` (SIGUSR1), or `queue`.
+    #[arg(long, value_parser = parse_on_busy, default_value = "restart")]
+    on_busy: OnBusyPolicy,
+
+    /// Seconds to wait after forwarding SIGTERM to the sandboxed process
+    /// group before escalating to SIGKILL.
+    #[arg(long, default_value_t = 10)]
+    shutdown_timeout: u64,
+
+    /// Seconds to wait after a watch-mode restart's SIGTERM (or, on Windows,
+    /// after asking the child to exit) before force-killing it.
+    #[arg(long, default_value_t = 5)]
+    grace_period: u64,
+
+    /// Only signal/kill the directly-spawned process on Unix instead of its
+    /// whole process group, leaving any grandchildren it forks to run on
+    /// after it's gone. Off by default since orphaned sandbox children are a
+    /// real correctness problem.
+    #[arg(long)]
+    no_process_group_kill: bool,
+
+    /// Run a declarative, ordered list of steps (JSON) against one
+    /// constructed sandbox instead of a single command. Mutually exclusive
+    /// with the trailing `command` and with `--watch`.
+    #[arg(long, conflicts_with_all = ["watch", "command"])]
+    pipeline: Option<String>,
+
+    /// Write an NDJSON event stream (lifecycle + I/O accounting) to an
+    /// already-open file descriptor. Mutually exclusive with `--events-file`.
+    #[arg(long, conflicts_with = "events_file")]
+    events_fd: Option<i32>,
+
+    /// Write an NDJSON event stream (lifecycle + I/O accounting) to `path`,
+    /// creating it if necessary and appending otherwise.
+    #[arg(long)]
+    events_file: Option<String>,
+
+    /// Pass the launcher's own stdin through to the sandboxed command
+    /// instead of closing it immediately. Needed for REPLs, `cat`, or
+    /// anything else that reads from stdin.
+    #[arg(long)]
+    stdin_inherit: bool,
+
     #[arg(last = true)]
     command: Vec<String>,
 }
@@ -52,28 +138,83 @@ fn parse_key_val(s: &str) -> Result<(String, String), String> {
     Ok((s[..pos].to_string(), s[pos + 1..].to_string()))
 }
 
+fn parse_on_busy(s: &str) -> Result<OnBusyPolicy, String> {
+    s.parse()
+}
+
 #[tokio::main]
 async fn main() {
     let args = Args::parse();
 
-    if args.command.is_empty() {
+    if args.pipeline.is_none() && args.command.is_empty() {
         eprintln!("[Launcher] ERROR: No command provided");
         process::exit(98);
     }
 
-    let ctx = ExecutionContext {
+    let base_ctx = ExecutionContext {
         id: args.id,
         root_path: args.workspace,
-        cmd: args.command[0].clone(),
-        args: args.command[1..].to_vec(),
+        cmd: args.command.first().cloned().unwrap_or_default(),
+        args: args.command.get(1..).map(<[_]>::to_vec).unwrap_or_default(),
         env_vars: args.env.into_iter().collect(),
         cwd: args.cwd,
         allow_network: !args.no_net,
+        jobs: args.jobs,
+        sandbox_exec_path: args.sandbox_exec_path,
+        bwrap_path: args.bwrap_path,
+        shell_path: args.shell_path,
+        shutdown_timeout: Duration::from_secs(args.shutdown_timeout),
+        kill_process_group: !args.no_process_group_kill,
+        output_mode: OutputMode::Inherit,
+        stdin: if args.stdin_inherit {
+            StdinSource::Inherit
+        } else {
+            StdinSource::Null
+        },
     };
 
-    let engine = Engine::new(args.sandbox);
+    let mut engine = Engine::new(args.sandbox).with_grace_period(Duration::from_secs(args.grace_period));
+
+    let events_sink = if let Some(path) = args.events_file {
+        EventSink::from_path(&path)
+    } else if let Some(fd) = args.events_fd {
+        #[cfg(unix)]
+        {
+            EventSink::from_fd(fd)
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = fd;
+            Err(anyhow::anyhow!("--events-fd is not supported on this platform; use --events-file"))
+        }
+    } else {
+        Ok(EventSink::disabled())
+    };
+    match events_sink {
+        Ok(sink) => engine = engine.with_events(sink),
+        Err(e) => {
+            eprintln!("[Launcher] FATAL ERROR: {e:#}");
+            process::exit(99);
+        }
+    }
+
+    let result = if let Some(pipeline_path) = args.pipeline {
+        match PipelineSpec::load(&pipeline_path) {
+            Ok(spec) => engine.run_pipeline(base_ctx, spec.steps).await,
+            Err(e) => Err(e),
+        }
+    } else if args.watch {
+        let watch_opts = WatchOptions {
+            watch_path: args.watch_path,
+            debounce: Duration::from_millis(args.debounce_ms),
+            on_busy: args.on_busy,
+        };
+        engine.watch(base_ctx, watch_opts).await
+    } else {
+        engine.run(base_ctx).await.map(|output| output.code)
+    };
 
-    match engine.run(ctx).await {
+    match result {
         Ok(code) => process::exit(code),
         Err(e) => {
             eprintln!("[Launcher] FATAL ERROR: {e:#}");
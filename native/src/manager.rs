@@ -0,0 +1,191 @@
+//! Supervises many concurrently running sandboxed executions with
+//! centralized shutdown, for embedding applications that orchestrate a batch
+//! of sandboxes instead of the CLI's one-process-per-invocation model.
+//!
+//! Not wired into the `workspace_launcher` CLI, which only ever drives one
+//! top-level execution per process; nothing in this binary calls it, hence
+//! the `dead_code` allowance below.
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{anyhow, Result};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+use crate::engine::Engine;
+use crate::strategies::base::ExecutionContext;
+
+/// Lifecycle state of a child tracked by a [`Manager`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChildState {
+    /// Spawned and not yet asked to terminate.
+    Running,
+    /// Asked to terminate (via `Manager::stop`); not yet exited.
+    Terminating,
+    /// Exited with this code (`Engine::run_managed`'s own sentinel for
+    /// force-killed children included).
+    Exited(i32),
+}
+
+/// `children`'s handles plus the flag that gates `spawn`, guarded by a
+/// single lock so a `spawn` that's already decided to proceed can never lose
+/// a race against `stop`'s drain — either `spawn` wins the lock and its
+/// handle is in `handles` before `stop` ever drains them, or `stop` wins the
+/// lock first and `spawn` sees `shutting_down` and refuses.
+#[derive(Default)]
+struct Children {
+    handles: Vec<JoinHandle<()>>,
+    shutting_down: bool,
+}
+
+impl Children {
+    /// Records `handle` unless shutdown has already begun, returning
+    /// whether it was accepted.
+    fn try_push(&mut self, handle: JoinHandle<()>) -> bool {
+        if self.shutting_down {
+            return false;
+        }
+        self.handles.push(handle);
+        true
+    }
+}
+
+/// Marks every currently-`Running` entry in `state` as `Terminating`, in
+/// place. Children already `Exited` are left alone.
+fn mark_terminating(state: &mut HashMap<String, ChildState>) {
+    for v in state.values_mut() {
+        if *v == ChildState::Running {
+            *v = ChildState::Terminating;
+        }
+    }
+}
+
+/// Owns a set of concurrently running sandboxed executions. A single
+/// SIGINT/SIGTERM (or an explicit `stop()` call) is broadcast to every live
+/// child at once, each of which runs the same SIGTERM-then-SIGKILL
+/// escalation `Engine::run` uses for a lone process. Once shutdown has
+/// begun, `spawn` refuses further children.
+pub struct Manager {
+    engine: Arc<Engine>,
+    state: Arc<Mutex<HashMap<String, ChildState>>>,
+    children: Mutex<Children>,
+    shutdown_tx: watch::Sender<bool>,
+    shutdown_rx: watch::Receiver<bool>,
+}
+
+impl Manager {
+    /// Creates a `Manager` that spawns all its children through `engine`.
+    #[must_use]
+    pub fn new(engine: Engine) -> Self {
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        Manager {
+            engine: Arc::new(engine),
+            state: Arc::new(Mutex::new(HashMap::new())),
+            children: Mutex::new(Children::default()),
+            shutdown_tx,
+            shutdown_rx,
+        }
+    }
+
+    /// Spawns `ctx` as a new managed child, returning its id for later
+    /// `state` lookups. Refuses once `stop` has been called.
+    pub fn spawn(&self, ctx: ExecutionContext) -> Result<String> {
+        let id = ctx.id.clone();
+
+        // Held from the shutdown check through the handle push — both are
+        // synchronous, so this never blocks across an `.await` — so `stop`
+        // can never observe `handles` in between (see `Children`'s doc).
+        let mut children = self.children.lock().unwrap();
+        if children.shutting_down {
+            return Err(anyhow!(
+                "Manager is shutting down; refusing to spawn `{}`",
+                id
+            ));
+        }
+
+        let engine = Arc::clone(&self.engine);
+        let state = Arc::clone(&self.state);
+        let shutdown_rx = self.shutdown_rx.clone();
+        let task_id = id.clone();
+
+        self.state
+            .lock()
+            .unwrap()
+            .insert(id.clone(), ChildState::Running);
+        let handle = tokio::spawn(async move {
+            let result = engine.run_managed(ctx, shutdown_rx).await;
+            let code = result.map(|output| output.code).unwrap_or(-1);
+            state.lock().unwrap().insert(task_id, ChildState::Exited(code));
+        });
+        let accepted = children.try_push(handle);
+        debug_assert!(accepted, "shutting_down can't flip while `children` is locked");
+
+        Ok(id)
+    }
+
+    /// Current lifecycle state of a managed child, or `None` if `id` was
+    /// never spawned through this `Manager`.
+    #[must_use]
+    pub fn state(&self, id: &str) -> Option<ChildState> {
+        self.state.lock().unwrap().get(id).copied()
+    }
+
+    /// Waits for the first SIGINT/SIGTERM (Ctrl-C on Windows) and then runs
+    /// the same shutdown `stop` performs. Intended to be spawned once per
+    /// `Manager`, alongside any number of `spawn` calls.
+    pub async fn run_until_signal(&self) {
+        crate::engine::wait_for_termination().await;
+        self.stop().await;
+    }
+
+    /// Broadcasts a shutdown to every live child and waits for all of them
+    /// (including any that have already exited) to reach a terminal state.
+    /// Further `spawn` calls are refused from the moment this is called.
+    pub async fn stop(&self) {
+        // Set before broadcasting so no `spawn` racing this call can sneak a
+        // handle past `close`'s drain below (see `Children`'s doc).
+        self.children.lock().unwrap().shutting_down = true;
+
+        {
+            let mut state = self.state.lock().unwrap();
+            mark_terminating(&mut state);
+        }
+        let _ = self.shutdown_tx.send(true);
+        self.close().await;
+    }
+
+    /// Waits for every spawned child to reach a terminal state without
+    /// signaling shutdown itself. Use `stop` to also request termination.
+    pub async fn close(&self) {
+        let handles = std::mem::take(&mut self.children.lock().unwrap().handles);
+        for handle in handles {
+            let _ = handle.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mark_terminating_only_touches_running() {
+        let mut state = HashMap::new();
+        state.insert("a".to_string(), ChildState::Running);
+        state.insert("b".to_string(), ChildState::Exited(0));
+        mark_terminating(&mut state);
+        assert_eq!(state["a"], ChildState::Terminating);
+        assert_eq!(state["b"], ChildState::Exited(0));
+    }
+
+    #[tokio::test]
+    async fn children_refuses_push_after_shutdown() {
+        let mut children = Children::default();
+        assert!(children.try_push(tokio::spawn(async {})));
+        children.shutting_down = true;
+        assert!(!children.try_push(tokio::spawn(async {})));
+        assert_eq!(children.handles.len(), 1);
+    }
+}
@@ -0,0 +1,48 @@
+//! Linux pidfd-based child-exit notification (`pidfd_open(2)`, syscall 434).
+//!
+//! The default `child.wait()` path relies on tokio's process-global SIGCHLD
+//! reaper, which can interact badly when this engine is embedded inside
+//! another application that reaps its own children. A pidfd is specific to
+//! one process, so polling it for readiness is race-free with respect to
+//! everyone else's SIGCHLD handling; the actual exit status is still
+//! collected through the normal `Child::try_wait`/`wait` path once the fd
+//! says it's safe to do so.
+
+use std::io;
+use std::os::fd::{FromRawFd, OwnedFd, RawFd};
+use tokio::io::unix::AsyncFd;
+
+/// An open pidfd for a still-running (or just-exited) child, wrapped for
+/// async readiness polling.
+pub struct PidFd(AsyncFd<OwnedFd>);
+
+impl PidFd {
+    /// Opens a pidfd for `pid` via `pidfd_open(2)`.
+    ///
+    /// Returns `Ok(None)` when the running kernel doesn't implement the
+    /// syscall (`ENOSYS`, kernel < 5.3), so callers can fall back to the
+    /// default `child.wait()` path; any other error is returned as-is.
+    pub fn open(pid: u32) -> io::Result<Option<Self>> {
+        // SAFETY: `pidfd_open` takes a pid and a flags word (0, no special
+        // behavior requested) and returns a new fd owned by this process, or
+        // -1 with errno set.
+        let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, pid as libc::pid_t, 0) };
+        if fd < 0 {
+            return match io::Error::last_os_error().raw_os_error() {
+                Some(libc::ENOSYS) => Ok(None),
+                _ => Err(io::Error::last_os_error()),
+            };
+        }
+        // SAFETY: `fd` was just returned by `pidfd_open` above; ownership
+        // transfers to the `OwnedFd` we construct here.
+        let owned = unsafe { OwnedFd::from_raw_fd(fd as RawFd) };
+        Ok(Some(PidFd(AsyncFd::new(owned)?)))
+    }
+
+    /// Resolves once the process this pidfd was opened for has exited. A
+    /// pidfd becomes (and stays) readable exactly when its process exits, so
+    /// one readiness wait is all that's needed.
+    pub async fn exited(&self) {
+        let _ = self.0.readable().await;
+    }
+}
@@ -0,0 +1,44 @@
+//! `--pipeline` support: a declarative, ordered list of steps run against one
+//! constructed isolation strategy instead of re-launching the binary per step.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// A single step in a `--pipeline` file.
+#[derive(Debug, Clone, Deserialize)]
+pub struct PipelineStep {
+    /// The binary or command to execute for this step.
+    pub cmd: String,
+
+    /// Arguments for `cmd`.
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Extra environment variables for this step, merged over the launcher's own.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+
+    /// Working directory override for this step. Falls back to the
+    /// top-level `--cwd`/workspace root when absent.
+    pub cwd: Option<String>,
+
+    /// If true, a nonzero exit code doesn't stop the pipeline.
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+/// The parsed contents of a `--pipeline <file>`.
+#[derive(Debug, Deserialize)]
+pub struct PipelineSpec {
+    pub steps: Vec<PipelineStep>,
+}
+
+impl PipelineSpec {
+    /// Reads and parses a pipeline file from disk.
+    pub fn load(path: &str) -> Result<Self> {
+        let data = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read pipeline file `{path}`"))?;
+        serde_json::from_str(&data).with_context(|| format!("failed to parse pipeline file `{path}`"))
+    }
+}
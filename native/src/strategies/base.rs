@@ -1,11 +1,65 @@
 use anyhow::Result;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::process::Command;
+use std::time::Duration;
+use tokio::io::AsyncRead;
+
+#[cfg(windows)]
+use crate::strategies::windows::WindowsJob;
+
+/// How `Engine::run` handles a child's stdout/stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputMode {
+    /// Stream straight to the launcher's own stdout/stderr (the default);
+    /// `RunOutput::stdout`/`stderr` are left empty.
+    #[default]
+    Inherit,
+    /// Accumulate into `RunOutput::stdout`/`stderr` instead of printing.
+    Capture,
+    /// Both stream to the launcher's stdout/stderr and accumulate into
+    /// `RunOutput::stdout`/`stderr`.
+    Tee,
+}
+
+/// What to feed the child's stdin.
+#[derive(Default)]
+pub enum StdinSource {
+    /// Close the child's stdin immediately (the default).
+    #[default]
+    Null,
+    /// Pass through the launcher's own stdin unchanged.
+    Inherit,
+    /// Write this buffer to the child's stdin, then close it.
+    Bytes(Vec<u8>),
+    /// Stream this reader to the child's stdin, then close it.
+    Reader(Box<dyn AsyncRead + Send + Unpin>),
+}
+
+impl Clone for StdinSource {
+    /// `Reader` is inherently single-use (it can't be read twice), so
+    /// cloning one degrades to `Null` rather than failing to compile or
+    /// silently sharing a reader across two spawns. This matters for
+    /// `Engine::watch`, which clones/reuses `ExecutionContext` across
+    /// restarts, and for `Engine::run_pipeline`, which clones its base
+    /// context for every step; `Engine::run` takes ownership of `stdin`
+    /// directly instead.
+    fn clone(&self) -> Self {
+        match self {
+            StdinSource::Null => StdinSource::Null,
+            StdinSource::Inherit => StdinSource::Inherit,
+            StdinSource::Bytes(bytes) => StdinSource::Bytes(bytes.clone()),
+            StdinSource::Reader(_) => StdinSource::Null,
+        }
+    }
+}
 
 /// Context provided to the isolation strategy to build the command.
+#[derive(Clone)]
 pub struct ExecutionContext {
-    /// Unique identifier for this process execution (useful for logging/debugging).
-    #[allow(dead_code)] // Kept for future observability features
+    /// Unique identifier for this process execution, threaded through every
+    /// emitted event so callers can correlate the NDJSON stream with the run
+    /// that produced it.
     pub id: String,
 
     /// The absolute path to the workspace root.
@@ -25,6 +79,63 @@ pub struct ExecutionContext {
 
     /// Whether to allow network access in the sandbox.
     pub allow_network: bool,
+
+    /// Total build parallelism to share across sandboxed build tools via a
+    /// GNU Make jobserver, if set. `None` disables jobserver coordination.
+    pub jobs: Option<usize>,
+
+    /// Override for the `sandbox-exec` binary used by `MacOsSandboxStrategy`.
+    pub sandbox_exec_path: Option<String>,
+
+    /// Override for the `bwrap` binary used by `LinuxBwrapStrategy`.
+    pub bwrap_path: Option<String>,
+
+    /// Override for the fallback shell used to run shell builtins on
+    /// `HostStrategy`/`WindowsJobStrategy`.
+    pub shell_path: Option<String>,
+
+    /// How long to wait after forwarding SIGTERM to the child's process
+    /// group before escalating to SIGKILL.
+    pub shutdown_timeout: Duration,
+
+    /// Whether signals/kills should target the child's whole process group
+    /// (Unix) rather than just the directly-spawned process, so descendants
+    /// the sandboxed command forks (bwrap, a shell wrapper, etc.) are torn
+    /// down too. Defaults to `true`; set `false` to opt out.
+    pub kill_process_group: bool,
+
+    /// How the child's stdout/stderr should be handled by `Engine::run`.
+    pub output_mode: OutputMode,
+
+    /// What to feed the child's stdin.
+    pub stdin: StdinSource,
+}
+
+/// Resolves a sandbox helper binary: an explicit override wins outright,
+/// otherwise fall back to a `PATH` lookup for `bin_name`. Returns `None` if
+/// neither source finds it, leaving the caller to apply its own default.
+///
+/// This mirrors how distributions substitute absolute tool paths rather than
+/// relying on well-known locations being present.
+pub fn resolve_tool_path(override_path: Option<&str>, bin_name: &str) -> Option<PathBuf> {
+    if let Some(p) = override_path {
+        return Some(PathBuf::from(p));
+    }
+    which::which(bin_name).ok()
+}
+
+/// Resolves `program` to the path a strategy's `Command` should run.
+///
+/// `shell` (`--shell-path`/`WORKSPACE_SANDBOX_SHELL_PATH`'s effective value)
+/// is a known-good implicit lookup, so skip `which` when `program` is
+/// exactly that shell; any other program is resolved against `PATH`,
+/// falling back to the bare name unchanged if `which` can't find it.
+pub fn resolve_program(program: &str, shell: &str) -> PathBuf {
+    if program == shell {
+        PathBuf::from(program)
+    } else {
+        which::which(program).unwrap_or_else(|_| PathBuf::from(program))
+    }
 }
 
 /// Trait that every platform-specific isolation strategy must implement.
@@ -36,4 +147,23 @@ pub trait IsolationStrategy {
     /// Returns the display name of the strategy (e.g., "Linux Bubblewrap").
     #[allow(dead_code)] // Used for internal logging/debugging
     fn name(&self) -> &str;
+
+    /// Sets up whatever teardown mechanism `child` — the process a
+    /// `build_command` `Command` was just spawned into — needs, and hands
+    /// the caller back a handle that can force a full teardown later.
+    ///
+    /// Only `WindowsJobStrategy` overrides this: a job object can't be
+    /// assigned a process that doesn't exist yet, so it's created and
+    /// assigned here, once `child` exists, rather than in `build_command`.
+    /// Creating it per call (instead of on strategy state shared across
+    /// concurrent spawns, e.g. through `Manager`) keeps each child's job
+    /// from racing another's. The returned handle lets `Engine` call
+    /// `TerminateJobObject` instead of `TerminateProcess`-ing just `child`
+    /// when it has to force a kill. Unix strategies don't need this:
+    /// `kill_process_group` already lets `Engine` target the child's whole
+    /// process group directly.
+    #[cfg(windows)]
+    fn assign_job(&self, _child: &tokio::process::Child) -> Option<WindowsJob> {
+        None
+    }
 }
@@ -1,9 +1,8 @@
 //! Fallback strategy that executes commands without isolation.
 
-use super::base::{ExecutionContext, IsolationStrategy};
+use super::base::{resolve_program, ExecutionContext, IsolationStrategy};
 use anyhow::Result;
 use std::process::{Command, Stdio};
-use which::which;
 
 pub struct HostStrategy;
 
@@ -15,6 +14,7 @@ impl IsolationStrategy for HostStrategy {
     fn build_command(&self, ctx: &ExecutionContext) -> Result<Command> {
         let mut program = ctx.cmd.clone();
         let mut args = ctx.args.clone();
+        let shell = ctx.shell_path.clone().unwrap_or_else(|| "cmd".to_string());
 
         if cfg!(windows) {
             let cmd_lower = program.to_lowercase();
@@ -25,15 +25,11 @@ impl IsolationStrategy for HostStrategy {
             if builtins.contains(&cmd_lower.as_str()) {
                 args.insert(0, "/c".to_string());
                 args.insert(1, program.clone());
-                program = "cmd".to_string();
+                program = shell.clone();
             }
         }
 
-        let resolved_program = if program == "cmd" {
-            "cmd".into()
-        } else {
-            which(&program).unwrap_or_else(|_| program.clone().into())
-        };
+        let resolved_program = resolve_program(&program, &shell);
 
         let mut command = Command::new(resolved_program);
         command
@@ -1,12 +1,11 @@
 //! Linux isolation using Bubblewrap with root passthrough strategy.
 
-use super::base::{ExecutionContext, IsolationStrategy};
+use super::base::{resolve_tool_path, ExecutionContext, IsolationStrategy};
 use anyhow::{Context, Result};
 use std::env;
 use std::fs;
 use std::path::Path;
 use std::process::{Command, Stdio};
-use which::which;
 
 pub struct LinuxBwrapStrategy;
 
@@ -17,8 +16,10 @@ impl IsolationStrategy for LinuxBwrapStrategy {
 
     #[allow(clippy::too_many_lines)]
     fn build_command(&self, ctx: &ExecutionContext) -> Result<Command> {
-        let bwrap_path = which("bwrap")
-            .context("bwrap not found. Install with: sudo apt install bubblewrap")?;
+        let bwrap_path = resolve_tool_path(ctx.bwrap_path.as_deref(), "bwrap").context(
+            "bwrap not found. Install with: sudo apt install bubblewrap, \
+             or pass --bwrap-path / set WORKSPACE_SANDBOX_BWRAP_PATH",
+        )?;
         let mut command = Command::new(bwrap_path);
 
         command
@@ -129,6 +130,25 @@ impl IsolationStrategy for LinuxBwrapStrategy {
         for (key, val) in &ctx.env_vars {
             command.env(key, val);
         }
+        // `MAKEFLAGS`/`CARGO_MAKEFLAGS`, when the engine set up a jobserver,
+        // land here like any other env var. Bubblewrap closes every fd but
+        // 0/1/2 before exec'ing `ctx.cmd` as a security measure, so the pipe
+        // fds those variables reference have to be named explicitly via
+        // `--forward-fd` or the sandboxed process would get a dead auth
+        // string pointing at closed fds.
+        for var in ["MAKEFLAGS", "CARGO_MAKEFLAGS"] {
+            if let Some(auth) = ctx
+                .env_vars
+                .get(var)
+                .and_then(|v| v.strip_prefix("--jobserver-auth="))
+            {
+                if let Some((read_fd, write_fd)) = auth.split_once(',') {
+                    command.arg("--forward-fd").arg(read_fd);
+                    command.arg("--forward-fd").arg(write_fd);
+                }
+                break;
+            }
+        }
 
         command
             .arg("--")
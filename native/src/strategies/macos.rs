@@ -1,9 +1,9 @@
 //! macOS isolation using Seatbelt (sandbox-exec).
 
-use super::base::{ExecutionContext, IsolationStrategy};
+use super::base::{resolve_tool_path, ExecutionContext, IsolationStrategy};
 use anyhow::Result;
 use std::env;
-use std::path::Path;
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
 
 pub struct MacOsSandboxStrategy;
@@ -14,10 +14,15 @@ impl IsolationStrategy for MacOsSandboxStrategy {
     }
 
     fn build_command(&self, ctx: &ExecutionContext) -> Result<Command> {
-        let sandbox_exec = "/usr/bin/sandbox-exec";
+        let sandbox_exec = resolve_tool_path(ctx.sandbox_exec_path.as_deref(), "sandbox-exec")
+            .unwrap_or_else(|| PathBuf::from("/usr/bin/sandbox-exec"));
 
-        if !Path::new(sandbox_exec).exists() {
-            return Err(anyhow::anyhow!("sandbox-exec not found on this system"));
+        if !sandbox_exec.exists() {
+            return Err(anyhow::anyhow!(
+                "sandbox-exec not found at `{}`. Pass --sandbox-exec-path / set \
+                 WORKSPACE_SANDBOX_SANDBOX_EXEC_PATH",
+                sandbox_exec.display()
+            ));
         }
 
         let home = env::var("HOME").unwrap_or_else(|_| "/var/tmp".to_string());
@@ -1,19 +1,66 @@
 //! Windows isolation using Job Objects for process grouping.
 
-use super::base::{ExecutionContext, IsolationStrategy};
+use super::base::{resolve_program, ExecutionContext, IsolationStrategy};
 use anyhow::Result;
 use std::process::{Command, Stdio};
-use which::which;
 
+#[cfg(windows)]
+use std::os::windows::process::CommandExt;
+#[cfg(windows)]
+use windows::Win32::Foundation::{CloseHandle, HANDLE};
 #[cfg(windows)]
 use windows::Win32::System::JobObjects::{
     AssignProcessToJobObject, CreateJobObjectW, JobObjectExtendedLimitInformation,
-    SetInformationJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
+    SetInformationJobObject, TerminateJobObject, JOBOBJECT_EXTENDED_LIMIT_INFORMATION,
     JOB_OBJECT_LIMIT_BREAKAWAY_OK, JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE,
 };
 #[cfg(windows)]
-use windows::Win32::System::Threading::GetCurrentProcess;
+use windows::Win32::System::Threading::CREATE_NEW_PROCESS_GROUP;
+
+/// A job object created and assigned by [`WindowsJobStrategy::assign_job`]
+/// once the sandboxed child exists, so a forced kill can `TerminateJobObject`
+/// the whole tree — the child plus anything it spawned — instead of
+/// `TerminateProcess`-ing just the one process. Each spawn gets its own
+/// `WindowsJob`, created fresh by that spawn's own `assign_job` call, so
+/// concurrent spawns through a shared `WindowsJobStrategy` (e.g. via
+/// `Manager`) never contend over which job belongs to which child.
+#[cfg(windows)]
+pub struct WindowsJob(HANDLE);
+
+// `HANDLE` is an opaque kernel object reference; job object handles are safe
+// to use from any thread, which is all `Send`/`Sync` need to promise here.
+#[cfg(windows)]
+unsafe impl Send for WindowsJob {}
+#[cfg(windows)]
+unsafe impl Sync for WindowsJob {}
+
+#[cfg(windows)]
+impl WindowsJob {
+    /// Terminates every process assigned to this job, i.e. the sandboxed
+    /// child and anything it spawned.
+    pub fn terminate(&self) {
+        // SAFETY: `self.0` is a job object handle created by `assign_job`
+        // and still open (we hold the only copy of it).
+        unsafe {
+            let _ = TerminateJobObject(self.0, 1);
+        }
+    }
+}
 
+#[cfg(windows)]
+impl Drop for WindowsJob {
+    fn drop(&mut self) {
+        // SAFETY: `self.0` is a job object handle created by `assign_job`
+        // and still open (we hold the only copy of it); closing it here is
+        // what stops every job object created over a long-running
+        // `--watch`/`Manager` lifetime from leaking.
+        unsafe {
+            let _ = CloseHandle(self.0);
+        }
+    }
+}
+
+#[derive(Default)]
 pub struct WindowsJobStrategy;
 
 impl IsolationStrategy for WindowsJobStrategy {
@@ -25,6 +72,7 @@ impl IsolationStrategy for WindowsJobStrategy {
         let mut program = ctx.cmd.clone();
         let mut args = ctx.args.clone();
         let prog_lower = program.to_lowercase();
+        let shell = ctx.shell_path.clone().unwrap_or_else(|| "cmd".to_string());
 
         let builtins = [
             "echo", "dir", "del", "copy", "move", "mkdir", "rmdir", "type", "cls", "ping", "ver",
@@ -34,18 +82,20 @@ impl IsolationStrategy for WindowsJobStrategy {
         if builtins.contains(&prog_lower.as_str()) || is_batch {
             args.insert(0, "/c".to_string());
             args.insert(1, program.clone());
-            program = "cmd".to_string();
+            program = shell.clone();
         }
 
-        let resolved_program = if program == "cmd" {
-            "cmd".into()
-        } else {
-            which(&program).unwrap_or_else(|_| program.clone().into())
-        };
+        let resolved_program = resolve_program(&program, &shell);
 
         let mut command = Command::new(resolved_program);
         command.args(&args);
 
+        // Its own process group, so a `--watch` restart's graceful stop can
+        // target exactly this child (and whatever it forks) with
+        // `CTRL_BREAK_EVENT` without also hitting the launcher's console.
+        #[cfg(windows)]
+        command.creation_flags(CREATE_NEW_PROCESS_GROUP.0);
+
         command.env_clear();
         let critical_vars = [
             "SystemRoot",
@@ -84,23 +134,35 @@ impl IsolationStrategy for WindowsJobStrategy {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
-        #[cfg(windows)]
-        unsafe {
-            let job = CreateJobObjectW(None, None)?;
-            let mut info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
-            info.BasicLimitInformation.LimitFlags =
-                JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE | JOB_OBJECT_LIMIT_BREAKAWAY_OK;
+        Ok(command)
+    }
 
+    #[cfg(windows)]
+    fn assign_job(&self, child: &tokio::process::Child) -> Option<WindowsJob> {
+        let handle = child.raw_handle()?;
+        // SAFETY: freshly created job object, checked via `?` below.
+        let job = unsafe { CreateJobObjectW(None, None).ok()? };
+        let mut info = JOBOBJECT_EXTENDED_LIMIT_INFORMATION::default();
+        info.BasicLimitInformation.LimitFlags =
+            JOB_OBJECT_LIMIT_KILL_ON_JOB_CLOSE | JOB_OBJECT_LIMIT_BREAKAWAY_OK;
+        // SAFETY: `job` was just created above and `info` outlives the call.
+        unsafe {
             SetInformationJobObject(
                 job,
                 JobObjectExtendedLimitInformation,
                 &info as *const _ as *const _,
                 std::mem::size_of::<JOBOBJECT_EXTENDED_LIMIT_INFORMATION>() as u32,
-            )?;
-
-            AssignProcessToJobObject(job, GetCurrentProcess())?;
+            )
+            .ok()?;
         }
-
-        Ok(command)
+        // SAFETY: `handle` is the live child this call was invoked for, and
+        // `job` is the job object just created for it above — each spawn
+        // creates and assigns its own job here, rather than stashing one on
+        // shared strategy state, so concurrent spawns (e.g. through
+        // `Manager`) never race over whose job is whose.
+        unsafe {
+            AssignProcessToJobObject(job, HANDLE(handle as isize)).ok()?;
+        }
+        Some(WindowsJob(job))
     }
 }
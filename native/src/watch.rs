@@ -0,0 +1,52 @@
+//! `--watch` support: restart the sandboxed command when the workspace changes.
+
+use std::time::Duration;
+
+/// Policy applied when a filesystem change arrives while the previous child
+/// is still running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnBusyPolicy {
+    /// Terminate the current child and start a fresh run (the default).
+    Restart,
+    /// Forward the change to the running child as `SIGUSR1` instead of restarting it.
+    Signal,
+    /// Hold the change until the current child exits on its own, then restart.
+    Queue,
+}
+
+impl std::str::FromStr for OnBusyPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "restart" => Ok(OnBusyPolicy::Restart),
+            "signal" => Ok(OnBusyPolicy::Signal),
+            "queue" => Ok(OnBusyPolicy::Queue),
+            other => Err(format!(
+                "invalid --on-busy value `{other}` (expected restart|signal|queue)"
+            )),
+        }
+    }
+}
+
+/// Options controlling `Engine::watch`.
+pub struct WatchOptions {
+    /// Host-side path to watch recursively. Defaults to `ctx.root_path` (the
+    /// same directory the isolation strategy bind-mounts into the sandbox)
+    /// when `None`, since that's the only view of the workspace that exists
+    /// outside the child's namespace.
+    pub watch_path: Option<String>,
+
+    /// How long to keep draining change events after the first one before
+    /// acting, so a burst of saves collapses into a single restart.
+    pub debounce: Duration,
+
+    /// What to do about a change that arrives while the child is busy.
+    pub on_busy: OnBusyPolicy,
+}
+
+/// Default grace period between SIGTERM and a hard kill when tearing down a
+/// child (watch-mode restarts and cancellation, `Engine::run_managed`
+/// shutdowns; `Engine::run` uses `ctx.shutdown_timeout`). Overridable via
+/// `Engine::with_grace_period`/`--grace-period`.
+pub const RESTART_GRACE_PERIOD: Duration = Duration::from_secs(5);